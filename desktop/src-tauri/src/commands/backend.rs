@@ -1,6 +1,7 @@
 //! Backend Management Commands
 
 use crate::sidecar;
+use crate::sidecar::manager::{LogRecord, LogStream};
 use crate::state::{AppState, BackendStatus};
 use std::sync::Arc;
 use tauri::{AppHandle, State};
@@ -13,6 +14,12 @@ pub async fn get_backend_url(state: State<'_, Arc<AppState>>) -> Result<String,
         .ok_or_else(|| "Backend not running".to_string())
 }
 
+/// 获取反向代理的固定入口 URL（前端应优先连这个，以便跨后端重启保持稳定）
+#[tauri::command]
+pub async fn get_proxy_url() -> Result<String, String> {
+    Ok(crate::proxy::proxy_url())
+}
+
 /// 获取后端状态
 #[tauri::command]
 pub async fn get_backend_status(state: State<'_, Arc<AppState>>) -> Result<BackendStatus, String> {
@@ -34,3 +41,12 @@ pub async fn stop_backend(app: AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| e.to_string())
 }
+
+/// 获取最近的后端日志（可按流过滤）
+#[tauri::command]
+pub async fn get_backend_logs(
+    tail: usize,
+    stream: Option<LogStream>,
+) -> Result<Vec<LogRecord>, String> {
+    Ok(sidecar::manager::get_logs(tail, stream))
+}
@@ -1,9 +1,25 @@
 //! Configuration Commands
 
-use crate::state::{AppConfig, AppState};
+use crate::state::{AppConfig, AppState, ShortcutsConfig};
+use crate::system;
 use std::sync::Arc;
 use tauri::{AppHandle, Manager, State};
 
+/// 收集两套快捷键配置中取值不同的加速键，用于只预检真正变化的绑定。
+fn changed_accelerators<'a>(
+    current: &ShortcutsConfig,
+    next: &'a ShortcutsConfig,
+) -> Vec<&'a str> {
+    let mut changed = Vec::new();
+    if current.toggle_window != next.toggle_window {
+        changed.push(next.toggle_window.as_str());
+    }
+    if current.new_chat != next.new_chat {
+        changed.push(next.new_chat.as_str());
+    }
+    changed
+}
+
 /// 获取应用配置
 #[tauri::command]
 pub async fn get_app_config(state: State<'_, Arc<AppState>>) -> Result<AppConfig, String> {
@@ -13,11 +29,25 @@ pub async fn get_app_config(state: State<'_, Arc<AppState>>) -> Result<AppConfig
 /// 设置应用配置
 #[tauri::command]
 pub async fn set_app_config(
+    app: AppHandle,
     state: State<'_, Arc<AppState>>,
     config: AppConfig,
 ) -> Result<bool, String> {
+    // 先对发生变化的加速键做一次不改动现有注册的预检，冲突或无效即早早拒绝；
+    // 只校验变化项，避免未改动的绑定与自身冲突造成误报。
+    let current = state.get_config().shortcuts;
+    for accelerator in changed_accelerators(&current, &config.shortcuts) {
+        system::shortcuts::validate_shortcut(&app, accelerator).map_err(|e| e.to_string())?;
+    }
+
+    // 再按新的快捷键绑定重注册，冲突或无效则拒绝整次保存，
+    // 以免用户在偏好设置里悄无声息地丢掉一个快捷键。
+    system::shortcuts::apply_shortcuts(&app, &config.shortcuts).map_err(|e| e.to_string())?;
+
     state.set_config(config);
-    // TODO: 持久化配置到文件
+    state
+        .persist_config()
+        .map_err(|e| format!("Failed to persist config: {}", e))?;
     Ok(true)
 }
 
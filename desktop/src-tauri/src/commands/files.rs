@@ -3,7 +3,8 @@
 use log::info;
 use serde::Serialize;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 /// 文件信息
 #[derive(Debug, Serialize)]
@@ -64,3 +65,275 @@ pub async fn get_file_info(path: String) -> Result<FileInfo, String> {
         modified,
     })
 }
+
+/// 用系统默认程序打开文件或目录
+#[tauri::command]
+pub async fn open_path(path: String) -> Result<bool, String> {
+    info!("Opening path with default handler: {}", path);
+    spawn_detached(default_open_command(&path))
+}
+
+/// 用指定的已安装应用打开文件
+///
+/// `app_id` 的含义随平台而定：macOS 为 bundle identifier（如
+/// `com.apple.TextEdit`），Linux 接受 `.desktop` 文件名或可执行文件，
+/// Windows 接受可执行文件路径。
+#[tauri::command]
+pub async fn open_with(path: String, app_id: String) -> Result<bool, String> {
+    info!("Opening {} with {}", path, app_id);
+    spawn_detached(open_with_command(&path, &app_id))
+}
+
+/// 在原生文件管理器中高亮选中该文件
+#[tauri::command]
+pub async fn reveal_in_file_manager(path: String) -> Result<bool, String> {
+    info!("Revealing in file manager: {}", path);
+
+    #[cfg(target_os = "linux")]
+    {
+        // 优先走 freedesktop 的 FileManager1 D-Bus 接口，它会高亮文件；
+        // 这里必须等 dbus-send 跑完再判断：spawn 成功不代表方法调用成功，没有
+        // FileManager1 提供方时调用会报错，只有据此回退 xdg-open 才有意义。
+        if run_to_completion(reveal_command(&path)).unwrap_or(false) {
+            return Ok(true);
+        }
+        let parent = Path::new(&path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        return spawn_detached(default_open_command(&parent));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        spawn_detached(reveal_command(&path))
+    }
+}
+
+/// 构建“用默认程序打开”的命令
+fn default_open_command(path: &str) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("open");
+        cmd.arg(path);
+        cmd
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // start 是 cmd 内建命令；第一个引号参数是窗口标题占位符。
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", "", path]);
+        cmd
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(path);
+        cmd
+    }
+}
+
+/// 构建“用指定应用打开”的命令
+fn open_with_command(path: &str, app_id: &str) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("open");
+        cmd.args(["-b", app_id, path]);
+        cmd
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new(app_id);
+        cmd.arg(path);
+        cmd
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(desktop) = app_id.strip_suffix(".desktop") {
+            let mut cmd = Command::new("gtk-launch");
+            cmd.args([desktop, path]);
+            cmd
+        } else {
+            let mut cmd = Command::new(app_id);
+            cmd.arg(path);
+            cmd
+        }
+    }
+}
+
+/// 构建“在文件管理器中高亮”的命令
+#[cfg(not(target_os = "linux"))]
+fn reveal_command(path: &str) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("open");
+        cmd.args(["-R", path]);
+        cmd
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("explorer");
+        cmd.arg(format!("/select,{}", path));
+        cmd
+    }
+}
+
+/// 通过 FileManager1 的 `ShowItems` 高亮文件
+#[cfg(target_os = "linux")]
+fn reveal_command(path: &str) -> Command {
+    let uri = format!("file://{}", path);
+    let mut cmd = Command::new("dbus-send");
+    cmd.args([
+        "--session",
+        "--print-reply",
+        "--dest=org.freedesktop.FileManager1",
+        "--type=method_call",
+        "/org/freedesktop/FileManager1",
+        "org.freedesktop.FileManager1.ShowItems",
+    ]);
+    cmd.arg(format!("array:string:{}", uri));
+    cmd.arg("string:");
+    cmd
+}
+
+/// 启动子进程后不等待其退出；成功 spawn 即视为成功。
+fn spawn_detached(mut cmd: Command) -> Result<bool, String> {
+    sanitize_child_env(&mut cmd);
+    cmd.spawn()
+        .map(|_| true)
+        .map_err(|e| format!("Failed to launch: {}", e))
+}
+
+/// 启动子进程并等待其退出，返回是否以成功状态码结束。
+///
+/// 用于那些“spawn 成功 ≠ 操作成功”的命令（如 `dbus-send --print-reply`，无
+/// 提供方时会以非零码失败），调用方据此决定是否回退。
+#[cfg(target_os = "linux")]
+fn run_to_completion(mut cmd: Command) -> Result<bool, String> {
+    sanitize_child_env(&mut cmd);
+    cmd.status()
+        .map(|status| status.success())
+        .map_err(|e| format!("Failed to launch: {}", e))
+}
+
+/// PATH 风格变量的分隔符
+#[cfg(windows)]
+const PATHLIST_SEP: char = ';';
+#[cfg(not(windows))]
+const PATHLIST_SEP: char = ':';
+
+/// bundle 会注入这些动态链接/插件搜索变量，指向打包进来的库；原样传给
+/// 外部程序会让它加载我们的库而崩溃，spawn 前需要清理。
+const BUNDLE_INJECTED_VARS: &[&str] = &[
+    "LD_LIBRARY_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "GTK_PATH",
+    "GIO_MODULE_DIR",
+    "GDK_PIXBUF_MODULE_FILE",
+];
+
+/// 当前进程是否运行在某种沙箱/自包含 bundle 中
+fn in_sandbox() -> bool {
+    std::env::var_os("APPIMAGE").is_some()
+        || std::env::var_os("APPDIR").is_some()
+        || std::env::var_os("SNAP").is_some()
+        || Path::new("/.flatpak-info").exists()
+}
+
+/// 本次运行涉及的 bundle 根目录（落在这些目录下的 PATH 项需要剔除）
+fn bundle_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Some(dir) = std::env::var_os("APPDIR") {
+        roots.push(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("SNAP") {
+        roots.push(PathBuf::from(dir));
+    }
+    if Path::new("/.flatpak-info").exists() {
+        roots.push(PathBuf::from("/app"));
+    }
+    roots
+}
+
+/// 剥离或恢复 bundle 注入的环境变量，使外部程序在干净的环境中启动。
+fn sanitize_child_env(cmd: &mut Command) {
+    if !in_sandbox() {
+        return;
+    }
+
+    let roots = bundle_roots();
+    for var in BUNDLE_INJECTED_VARS {
+        let value = match std::env::var(var) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        // bundle 启动脚本通常会把宿主机原值备份到 ${VAR}_ORIG/${VAR}_OLD，
+        // 能拿到就直接还原，拿不到再按目录前缀过滤。
+        if let Some(orig) = std::env::var(format!("{}_ORIG", var))
+            .or_else(|_| std::env::var(format!("{}_OLD", var)))
+            .ok()
+        {
+            cmd.env(var, orig);
+            continue;
+        }
+
+        let cleaned = normalize_pathlist(&value, &roots);
+        if cleaned.is_empty() {
+            cmd.env_remove(var);
+        } else {
+            cmd.env(var, cleaned);
+        }
+    }
+}
+
+/// 规整一个 PATH 风格变量：按 OS 分隔符拆分，丢弃指向 bundle 根目录内的
+/// 条目，并去重（保留靠后、优先级更低的那一份）。
+pub fn normalize_pathlist(value: &str, bundle_roots: &[PathBuf]) -> String {
+    let kept: Vec<&str> = value
+        .split(PATHLIST_SEP)
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| {
+            let p = Path::new(entry);
+            !bundle_roots.iter().any(|root| p.starts_with(root))
+        })
+        .collect();
+
+    // 去重但保留后出现的一份：从尾部向前扫，记录首见的条目，再反转回来。
+    let mut seen = std::collections::HashSet::new();
+    let mut out: Vec<&str> = Vec::new();
+    for entry in kept.iter().rev() {
+        if seen.insert(*entry) {
+            out.push(entry);
+        }
+    }
+    out.reverse();
+    out.join(&PATHLIST_SEP.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_pathlist_drops_bundle_entries() {
+        let roots = vec![PathBuf::from("/tmp/.mount_app")];
+        let sep = PATHLIST_SEP;
+        let input = format!("/tmp/.mount_app/usr/lib{sep}/usr/lib{sep}/lib");
+        let out = normalize_pathlist(&input, &roots);
+        assert_eq!(out, format!("/usr/lib{sep}/lib"));
+    }
+
+    #[test]
+    fn test_normalize_pathlist_prefers_later_duplicate() {
+        let sep = PATHLIST_SEP;
+        let input = format!("/a{sep}/b{sep}/a");
+        // 重复的 /a 只保留靠后的那一份，相对顺序不变
+        assert_eq!(normalize_pathlist(&input, &[]), format!("/b{sep}/a"));
+    }
+}
@@ -0,0 +1,233 @@
+//! Local Control Socket
+//!
+//! Lets a second invocation of the binary (`okcvm backend status|restart|
+//! stop|url`) talk to the already-running GUI instance instead of opening a
+//! second window. The running app listens on a small local control channel
+//! — a Unix domain socket on Unix, a loopback TCP port recorded in a
+//! lockfile on Windows — and dispatches each command to
+//! [`crate::sidecar::manager`].
+
+use crate::sidecar;
+use crate::state::AppState;
+use crate::utils::paths;
+use log::{error, info};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// 控制通道套接字/锁文件所在路径
+fn control_path() -> Option<PathBuf> {
+    let dir = paths::get_app_data_dir()?;
+    #[cfg(windows)]
+    {
+        Some(dir.join("control.lock"))
+    }
+    #[cfg(not(windows))]
+    {
+        Some(dir.join("control.sock"))
+    }
+}
+
+/// 把一条命令分发给 sidecar 管理器，返回要写回客户端的 JSON 行。
+async fn dispatch(app: &AppHandle, command: &str) -> String {
+    let state = app.state::<Arc<AppState>>();
+    match command.trim() {
+        "status" => serde_json::to_string(&state.get_backend_status())
+            .unwrap_or_else(|e| error_json(&e.to_string())),
+        "url" => match state.get_backend_url() {
+            Some(url) => serde_json::json!({ "url": url }).to_string(),
+            None => error_json("backend not running"),
+        },
+        "restart" => match sidecar::manager::restart_backend(app).await {
+            Ok(_) => serde_json::to_string(&state.get_backend_status())
+                .unwrap_or_else(|e| error_json(&e.to_string())),
+            Err(e) => error_json(&e.to_string()),
+        },
+        "stop" => match sidecar::manager::stop_backend(app).await {
+            Ok(_) => serde_json::to_string(&state.get_backend_status())
+                .unwrap_or_else(|e| error_json(&e.to_string())),
+            Err(e) => error_json(&e.to_string()),
+        },
+        other => error_json(&format!("unknown command: {}", other)),
+    }
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// 在运行中的实例里启动控制服务（GUI 启动路径调用一次）。
+pub fn start_control_server(app: &AppHandle) {
+    let path = match control_path() {
+        Some(p) => p,
+        None => {
+            error!("Control socket path is unavailable; CLI control disabled");
+            return;
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = serve(&app, &path).await {
+            error!("Control server exited: {}", e);
+        }
+    });
+}
+
+#[cfg(not(windows))]
+async fn serve(app: &AppHandle, path: &std::path::Path) -> std::io::Result<()> {
+    use tokio::net::UnixListener;
+
+    // 清理上一轮残留的套接字文件，否则 bind 会因 AddrInUse 失败。
+    let _ = std::fs::remove_file(path);
+    let listener = UnixListener::bind(path)?;
+    info!("Control socket listening at {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = handle_conn(&app, stream).await;
+        });
+    }
+}
+
+#[cfg(windows)]
+async fn serve(app: &AppHandle, path: &std::path::Path) -> std::io::Result<()> {
+    use tokio::net::TcpListener;
+
+    // Windows 上用环回 TCP 端口，端口号写进锁文件供客户端读取。
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let port = listener.local_addr()?.port();
+    std::fs::write(path, port.to_string())?;
+    info!("Control server listening on 127.0.0.1:{}", port);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let _ = handle_conn(&app, stream).await;
+        });
+    }
+}
+
+async fn handle_conn<S>(app: &AppHandle, stream: S) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    if let Some(command) = lines.next_line().await? {
+        let mut response = dispatch(app, &command).await;
+        response.push('\n');
+        writer.write_all(response.as_bytes()).await?;
+        writer.flush().await?;
+    }
+    Ok(())
+}
+
+/// 若以 CLI 形式调用（`okcvm backend <subcommand>`），处理后返回退出码；
+/// 否则返回 `None`，让正常的 GUI 启动流程继续。
+pub fn maybe_run_cli() -> Option<i32> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 || args[1] != "backend" {
+        return None;
+    }
+
+    let subcommand = match args.get(2).map(|s| s.as_str()) {
+        Some(sub @ ("status" | "restart" | "stop" | "url")) => sub,
+        _ => {
+            eprintln!("usage: okcvm backend <status|restart|stop|url>");
+            return Some(2);
+        }
+    };
+
+    Some(run_client(subcommand))
+}
+
+#[cfg(not(windows))]
+fn run_client(subcommand: &str) -> i32 {
+    use std::os::unix::net::UnixStream;
+
+    let path = match control_path() {
+        Some(p) => p,
+        None => {
+            eprintln!("error: control socket path is unavailable");
+            return 1;
+        }
+    };
+
+    let stream = match UnixStream::connect(&path) {
+        Ok(stream) => stream,
+        Err(_) => {
+            eprintln!("error: no running OKCVM instance (control socket not reachable)");
+            return 1;
+        }
+    };
+
+    send_and_print(stream, subcommand)
+}
+
+#[cfg(windows)]
+fn run_client(subcommand: &str) -> i32 {
+    use std::net::TcpStream;
+
+    let path = match control_path() {
+        Some(p) => p,
+        None => {
+            eprintln!("error: control lockfile path is unavailable");
+            return 1;
+        }
+    };
+
+    let port = match std::fs::read_to_string(&path).ok().and_then(|s| s.trim().parse::<u16>().ok()) {
+        Some(port) => port,
+        None => {
+            eprintln!("error: no running OKCVM instance (control lockfile missing)");
+            return 1;
+        }
+    };
+
+    let stream = match TcpStream::connect(("127.0.0.1", port)) {
+        Ok(stream) => stream,
+        Err(_) => {
+            eprintln!("error: no running OKCVM instance (control port not reachable)");
+            return 1;
+        }
+    };
+
+    send_and_print(stream, subcommand)
+}
+
+/// 发送子命令并把服务端返回的 JSON 行打到 stdout
+fn send_and_print<S>(mut stream: S, subcommand: &str) -> i32
+where
+    S: std::io::Read + std::io::Write,
+{
+    use std::io::{BufRead, BufReader, Write};
+
+    if writeln!(stream, "{}", subcommand).is_err() {
+        eprintln!("error: failed to send command");
+        return 1;
+    }
+    let _ = stream.flush();
+
+    let mut response = String::new();
+    if BufReader::new(stream).read_line(&mut response).is_err() {
+        eprintln!("error: failed to read response");
+        return 1;
+    }
+
+    print!("{}", response);
+    // 服务端用 {"error": ...} 表示失败，据此给出非零退出码。
+    if response.contains("\"error\"") {
+        1
+    } else {
+        0
+    }
+}
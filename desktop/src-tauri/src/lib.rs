@@ -4,7 +4,10 @@
 //! including sidecar management, system integration, and state management.
 
 pub mod commands;
+pub mod ipc;
+pub mod proxy;
 pub mod sidecar;
 pub mod state;
 pub mod system;
+pub mod updater;
 pub mod utils;
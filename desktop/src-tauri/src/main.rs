@@ -2,9 +2,12 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod commands;
+mod ipc;
+mod proxy;
 mod sidecar;
 mod state;
 mod system;
+mod updater;
 mod utils;
 
 use log::{error, info};
@@ -13,6 +16,12 @@ use std::sync::Arc;
 use tauri::{Manager, SystemTray, SystemTrayEvent};
 
 fn main() {
+    // CLI 控制路径：若是 `okcvm backend <cmd>` 形式，转发给正在运行的实例
+    // 并退出，绝不走正常的 GUI 启动流程。
+    if let Some(code) = ipc::maybe_run_cli() {
+        std::process::exit(code);
+    }
+
     // 初始化日志
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
@@ -54,6 +63,18 @@ fn main() {
                 error!("Failed to register shortcuts: {}", e);
             }
 
+            // 启动系统主题监视器
+            system::theme::start_theme_watcher(&app.handle());
+
+            // 按配置启动自动更新检查
+            updater::start_auto_update(&app.handle());
+
+            // 启动本地控制套接字，供 CLI 子命令连接
+            ipc::start_control_server(&app.handle());
+
+            // 启动反向代理，给前端一个固定的后端入口
+            proxy::start_proxy(&app.handle());
+
             info!("Application setup complete");
             Ok(())
         })
@@ -67,16 +88,23 @@ fn main() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::backend::get_backend_url,
+            commands::backend::get_proxy_url,
             commands::backend::get_backend_status,
             commands::backend::restart_backend,
             commands::backend::stop_backend,
+            commands::backend::get_backend_logs,
             commands::files::read_local_file,
             commands::files::write_local_file,
             commands::files::get_file_info,
+            commands::files::open_path,
+            commands::files::open_with,
+            commands::files::reveal_in_file_manager,
             commands::config::get_app_config,
             commands::config::set_app_config,
             commands::config::get_app_version,
             commands::config::get_data_dir,
+            updater::check_for_update,
+            updater::download_and_install_update,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,259 @@
+//! Local Reverse Proxy
+//!
+//! Binds a *fixed* loopback address at startup and forwards every request to
+//! whatever dynamic address the current sidecar occupies, looking the target up
+//! from [`BackendStatus::Running`] on each request. This gives the webview a
+//! single stable origin for the app's lifetime: a backend restart (which picks
+//! a fresh port, or re-binds its socket) is invisible to callers, and requests
+//! that arrive while the backend is `Starting`/`Stopping` are fast-failed with a
+//! `503` and a `Retry-After` instead of a connection refusal.
+//!
+//! Bodies are streamed through in both directions rather than buffered, so
+//! SSE/chunked endpoints (token streaming from the agent layer) relay live
+//! instead of hanging until the upstream closes.
+
+use crate::state::{AppState, BackendStatus};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use log::{error, info};
+use once_cell::sync::Lazy;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+/// 反向代理对外暴露的固定地址
+pub const PROXY_ADDR: ([u8; 4], u16) = ([127, 0, 0, 1], 17650);
+
+/// 转发 TCP 后端时复用的单一客户端（连接池随之复用）
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+/// 代理前端可用的稳定 URL
+pub fn proxy_url() -> String {
+    let (ip, port) = PROXY_ADDR;
+    format!("http://{}.{}.{}.{}:{}", ip[0], ip[1], ip[2], ip[3], port)
+}
+
+/// 当前应转发到的后端目标
+enum BackendTarget {
+    /// TCP 传输：动态端口
+    Tcp(u16),
+    /// Unix 域套接字传输：套接字地址（文件路径或抽象命名空间）
+    #[cfg(unix)]
+    Unix(String),
+}
+
+/// 在应用启动时拉起反向代理。
+pub fn start_proxy(app: &AppHandle) {
+    let app = app.clone();
+    let addr = SocketAddr::from(PROXY_ADDR);
+
+    tauri::async_runtime::spawn(async move {
+        let make_svc = make_service_fn(move |_conn| {
+            let app = app.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let app = app.clone();
+                    async move { proxy_request(app, req).await }
+                }))
+            }
+        });
+
+        info!("Reverse proxy listening at {}", proxy_url());
+        if let Err(e) = Server::bind(&addr).serve(make_svc).await {
+            error!("Reverse proxy exited: {}", e);
+        }
+    });
+}
+
+/// 把一个请求转发到当前后端；后端不在 `Running` 时返回 503。
+async fn proxy_request(app: AppHandle, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    match backend_target(&app) {
+        Some(BackendTarget::Tcp(port)) => forward_tcp(port, req).await,
+        #[cfg(unix)]
+        Some(BackendTarget::Unix(socket)) => forward_uds(&socket, req).await,
+        // Starting/Stopping/重启窗口：让调用方稍后重试，而不是连接被拒
+        None => Ok(unavailable()),
+    }
+}
+
+/// 解析当前后端目标：`Running` 下优先用 TCP 端口，否则用 Unix 套接字。
+fn backend_target(app: &AppHandle) -> Option<BackendTarget> {
+    let state = app.try_state::<Arc<AppState>>()?;
+    match state.get_backend_status() {
+        BackendStatus::Running {
+            port: Some(port), ..
+        } => Some(BackendTarget::Tcp(port)),
+        #[cfg(unix)]
+        BackendStatus::Running {
+            socket: Some(socket),
+            ..
+        } => Some(BackendTarget::Unix(socket)),
+        _ => None,
+    }
+}
+
+/// 通过 reqwest 把请求流式转发到 TCP 后端。
+async fn forward_tcp(port: u16, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    let target = format!("http://127.0.0.1:{}{}", port, path);
+
+    let method = req.method().clone();
+    let mut headers = req.headers().clone();
+    // 逐跳首部只在单段连接内有效，转发前必须剥掉；框架相关首部交给 reqwest
+    // 按实际请求体重建
+    strip_hop_by_hop(&mut headers);
+    // 流式透传请求体，避免把 SSE/上传等长连接整条缓冲到内存
+    let body = reqwest::Body::wrap_stream(req.into_body());
+
+    let mut builder = CLIENT.request(method, &target).body(body);
+    for (name, value) in headers.iter() {
+        // Host 由上游根据目标地址重写，不要透传代理的 Host
+        if name.as_str().eq_ignore_ascii_case("host") {
+            continue;
+        }
+        builder = builder.header(name, value);
+    }
+
+    match builder.send().await {
+        Ok(upstream) => Ok(relay(upstream)),
+        // 后端刚好在本次请求期间倒下：同样走 503 让前端重试
+        Err(_) => Ok(unavailable()),
+    }
+}
+
+/// 通过 Unix 域套接字上的 HTTP/1 连接流式转发请求。
+#[cfg(unix)]
+async fn forward_uds(socket: &str, mut req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let stream = match connect_uds(socket).await {
+        Ok(stream) => stream,
+        // 套接字还没就绪/已消失：当作暂不可用让前端重试
+        Err(_) => return Ok(unavailable()),
+    };
+
+    let (mut sender, conn) = match hyper::client::conn::handshake(stream).await {
+        Ok(pair) => pair,
+        Err(e) => return Ok(bad_gateway(&e.to_string())),
+    };
+    // 连接任务独立驱动收发，直到请求-响应（含流式响应体）走完
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = conn.await {
+            error!("Proxy UDS connection error: {}", e);
+        }
+    });
+
+    // UDS 上用 origin-form（仅 path+query），并去掉代理侧的 Host
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/")
+        .to_string();
+    if let Ok(uri) = path.parse() {
+        *req.uri_mut() = uri;
+    }
+    req.headers_mut().remove(hyper::header::HOST);
+
+    match sender.send_request(req).await {
+        Ok(response) => Ok(response),
+        Err(_) => Ok(unavailable()),
+    }
+}
+
+/// 连接后端 Unix 套接字，兼容文件路径与 Linux 抽象命名空间（`@`/`\x00` 前缀）。
+#[cfg(unix)]
+async fn connect_uds(socket: &str) -> Result<tokio::net::UnixStream, String> {
+    match socket.strip_prefix("\\x00").or_else(|| socket.strip_prefix('@')) {
+        Some(name) => connect_abstract(name).await,
+        None => tokio::net::UnixStream::connect(socket)
+            .await
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// 连接 Linux 抽象命名空间套接字（名字带前导 NUL，无文件系统条目）。
+#[cfg(target_os = "linux")]
+async fn connect_abstract(name: &str) -> Result<tokio::net::UnixStream, String> {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::{SocketAddr, UnixStream as StdUnixStream};
+
+    let addr = SocketAddr::from_abstract_name(name.as_bytes()).map_err(|e| e.to_string())?;
+    let std_stream = StdUnixStream::connect_addr(&addr).map_err(|e| e.to_string())?;
+    std_stream.set_nonblocking(true).map_err(|e| e.to_string())?;
+    tokio::net::UnixStream::from_std(std_stream).map_err(|e| e.to_string())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+async fn connect_abstract(_name: &str) -> Result<tokio::net::UnixStream, String> {
+    Err("abstract unix sockets are only supported on Linux".to_string())
+}
+
+/// 把 reqwest 上游响应流式转换回 hyper 响应
+fn relay(upstream: reqwest::Response) -> Response<Body> {
+    let status = upstream.status();
+    let mut headers = upstream.headers().clone();
+    // reqwest 已对上游响应解码，原 `transfer-encoding`/`content-length` 会与
+    // hyper 自己的分块/长度框架冲突，逐跳首部也不应透传——统一剥掉
+    strip_hop_by_hop(&mut headers);
+    // 流式透传响应体，保证 SSE/分块能边到边发，而不是等上游关闭
+    let mut response = Response::new(Body::wrap_stream(upstream.bytes_stream()));
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    response
+}
+
+/// 剥除逐跳（hop-by-hop）首部及与重建框架冲突的长度/编码首部。
+///
+/// 包含 RFC 7230 §6.1 列出的固定逐跳首部、`Connection` 头显式点名的首部，以及
+/// `content-length`——响应体已被重新组装为流式 body，保留原长度会导致帧错乱。
+fn strip_hop_by_hop(headers: &mut hyper::HeaderMap) {
+    // `Connection` 头里点名的首部也属于逐跳首部
+    let listed: Vec<String> = headers
+        .get(hyper::header::CONNECTION)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(',')
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    const HOP_BY_HOP: [&str; 9] = [
+        "connection",
+        "keep-alive",
+        "proxy-authenticate",
+        "proxy-authorization",
+        "te",
+        "trailers",
+        "transfer-encoding",
+        "upgrade",
+        "content-length",
+    ];
+
+    for name in HOP_BY_HOP.iter().map(|s| s.to_string()).chain(listed) {
+        headers.remove(name.as_str());
+    }
+}
+
+/// 后端尚未就绪时的 503 响应，附带 `Retry-After`
+fn unavailable() -> Response<Body> {
+    let mut response = Response::new(Body::from("backend is not ready"));
+    *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+    response
+        .headers_mut()
+        .insert("retry-after", "1".parse().unwrap());
+    response
+}
+
+/// 转发上游时发生的错误
+fn bad_gateway(message: &str) -> Response<Body> {
+    let mut response = Response::new(Body::from(message.to_string()));
+    *response.status_mut() = StatusCode::BAD_GATEWAY;
+    response
+}
@@ -12,7 +12,118 @@ pub struct HealthResponse {
     pub version: Option<String>,
 }
 
-/// 检查后端健康状态
+/// 健康检查端点：TCP 回环或 Unix 域套接字。
+#[derive(Debug, Clone)]
+pub enum Endpoint {
+    /// `http://127.0.0.1:<port>/api/health`
+    Tcp(String),
+    /// Unix 域套接字；`abstract_ns` 为真时走 Linux 抽象命名空间。
+    Unix {
+        socket: String,
+        path: String,
+        abstract_ns: bool,
+    },
+}
+
+/// 按端点类型分发健康检查。
+pub async fn check_endpoint(endpoint: &Endpoint) -> Result<bool, String> {
+    match endpoint {
+        Endpoint::Tcp(url) => check_health(url).await,
+        Endpoint::Unix {
+            socket,
+            path,
+            abstract_ns,
+        } => check_health_uds(socket, path, *abstract_ns).await,
+    }
+}
+
+/// 解析 `HealthResponse` 的状态字段
+fn is_healthy(health: &HealthResponse) -> bool {
+    health.status == "healthy" || health.status == "ok"
+}
+
+/// 通过 Unix 域套接字做一次最小化的 HTTP/1.0 健康检查。
+#[cfg(unix)]
+pub async fn check_health_uds(
+    socket: &str,
+    request_path: &str,
+    abstract_ns: bool,
+) -> Result<bool, String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixStream;
+
+    let mut stream = if abstract_ns {
+        connect_abstract(socket).await?
+    } else {
+        UnixStream::connect(socket).await.map_err(|e| e.to_string())?
+    };
+
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        request_path
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut raw = Vec::new();
+    stream
+        .read_to_end(&mut raw)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let text = String::from_utf8_lossy(&raw);
+    // 拆出状态行与响应体
+    let mut parts = text.splitn(2, "\r\n\r\n");
+    let head = parts.next().unwrap_or("");
+    let body = parts.next().unwrap_or("");
+
+    let status_ok = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .map(|code| (200..300).contains(&code))
+        .unwrap_or(false);
+    if !status_ok {
+        return Ok(false);
+    }
+
+    let health: HealthResponse =
+        serde_json::from_str(body.trim()).map_err(|e| e.to_string())?;
+    debug!("Health check (uds) response: {:?}", health);
+    Ok(is_healthy(&health))
+}
+
+/// 连接 Linux 抽象命名空间套接字（名字带前导 NUL，无文件系统条目）。
+#[cfg(target_os = "linux")]
+async fn connect_abstract(name: &str) -> Result<tokio::net::UnixStream, String> {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::{SocketAddr, UnixStream as StdUnixStream};
+
+    let addr = SocketAddr::from_abstract_name(name.as_bytes()).map_err(|e| e.to_string())?;
+    let std_stream = StdUnixStream::connect_addr(&addr).map_err(|e| e.to_string())?;
+    std_stream.set_nonblocking(true).map_err(|e| e.to_string())?;
+    tokio::net::UnixStream::from_std(std_stream).map_err(|e| e.to_string())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+async fn connect_abstract(_name: &str) -> Result<tokio::net::UnixStream, String> {
+    Err("abstract unix sockets are only supported on Linux".to_string())
+}
+
+/// 非 Unix 平台没有 UDS 传输；保留同名桩以便 [`check_endpoint`] 始终可编译。
+#[cfg(not(unix))]
+pub async fn check_health_uds(
+    _socket: &str,
+    _request_path: &str,
+    _abstract_ns: bool,
+) -> Result<bool, String> {
+    Err("unix domain socket transport is not supported on this platform".to_string())
+}
+
+/// 检查后端健康状态（TCP）
 pub async fn check_health(url: &str) -> Result<bool, String> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(5))
@@ -29,7 +140,7 @@ pub async fn check_health(url: &str) -> Result<bool, String> {
 
     debug!("Health check response: {:?}", health);
 
-    Ok(health.status == "healthy" || health.status == "ok")
+    Ok(is_healthy(&health))
 }
 
 #[cfg(test)]
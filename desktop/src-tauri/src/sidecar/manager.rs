@@ -1,19 +1,32 @@
 //! Sidecar Process Manager
 //!
-//! Manages the lifecycle of the Python backend process.
+//! Manages the lifecycle of the Python backend and any additional helper
+//! processes. A single long-lived [`SidecarController`] owns a registry of
+//! named [`SidecarInstance`]s; each instance owns its own child handle,
+//! transport address, config, status, restart counter, log ring and
+//! output-reader task. The default `"okcvm-server"` instance mirrors its
+//! status into [`AppState`] so existing callers keep working unchanged.
 
 use super::health;
 use crate::state::{AppState, BackendStatus};
 use crate::utils::port::find_available_port;
+use dashmap::DashMap;
 use log::{error, info, warn};
+use parking_lot::Mutex as SyncMutex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::api::process::{Command, CommandChild, CommandEvent};
 use tauri::{AppHandle, Manager};
 use thiserror::Error;
 use tokio::sync::Mutex;
 use tokio::time::timeout;
 
+/// 默认后端 sidecar 名称
+pub const DEFAULT_SIDECAR: &str = "okcvm-server";
+
 /// Sidecar 管理错误
 #[derive(Debug, Error)]
 pub enum SidecarError {
@@ -36,14 +49,44 @@ pub enum SidecarError {
     OperationFailed(String),
 }
 
+/// sidecar 与前端之间的传输方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// 绑定到 `127.0.0.1:<port>` 的回环 TCP
+    Tcp,
+    /// Unix 域套接字（桌面单机部署更快也更私密）
+    UnixSocket,
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Transport::Tcp
+    }
+}
+
+impl Transport {
+    /// 解析 `BackendConfig.transport` 字段；未识别的取值回退到 TCP。
+    pub fn from_config(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "unix" | "uds" | "unixsocket" => Transport::UnixSocket,
+            _ => Transport::Tcp,
+        }
+    }
+}
+
 /// Sidecar 配置
+#[derive(Clone)]
 pub struct SidecarConfig {
+    /// 传输方式
+    pub transport: Transport,
     /// 健康检查间隔（毫秒）
     pub health_check_interval: u64,
     /// 启动超时（毫秒）
     pub startup_timeout: u64,
     /// 最大重启次数
     pub max_restart_attempts: u32,
+    /// 连续不健康多久后触发重启（毫秒）
+    pub unhealthy_timeout: u64,
     /// 端口范围起始
     pub port_range_start: u16,
     /// 端口范围结束
@@ -53,138 +96,645 @@ pub struct SidecarConfig {
 impl Default for SidecarConfig {
     fn default() -> Self {
         Self {
+            transport: Transport::default(),
             health_check_interval: 5000,
             startup_timeout: 30000,
             max_restart_attempts: 3,
+            unhealthy_timeout: 15000,
             port_range_start: 8000,
             port_range_end: 9000,
         }
     }
 }
 
-/// 全局 sidecar 子进程句柄
-static SIDECAR_CHILD: once_cell::sync::Lazy<Mutex<Option<CommandChild>>> =
-    once_cell::sync::Lazy::new(|| Mutex::new(None));
+/// 重启退避上限
+const BACKOFF_CAP: Duration = Duration::from_secs(4);
 
-/// 启动后端服务
-pub async fn start_backend(app: &AppHandle) -> Result<u16, SidecarError> {
-    let config = SidecarConfig::default();
-    let state = app.state::<Arc<AppState>>();
-
-    // 检查是否已在运行
-    if let BackendStatus::Running { port, .. } = state.get_backend_status() {
-        info!("Backend already running on port {}", port);
-        return Ok(port);
-    }
-
-    // 更新状态为启动中
-    state.set_backend_status(BackendStatus::Starting);
-
-    // 查找可用端口
-    let port = find_available_port(config.port_range_start, config.port_range_end)
-        .ok_or(SidecarError::PortError)?;
-
-    info!("Starting backend on port {}", port);
-
-    // 获取数据目录
-    let data_dir = app
-        .path_resolver()
-        .app_data_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."));
-
-    // 构建启动参数
-    let args = vec![
-        "--host".to_string(),
-        "127.0.0.1".to_string(),
-        "--port".to_string(),
-        port.to_string(),
-        "--data-dir".to_string(),
-        data_dir.to_string_lossy().to_string(),
-    ];
-
-    // 启动 sidecar 进程
-    let (mut rx, child) = Command::new_sidecar("okcvm-server")
-        .map_err(|e| SidecarError::SpawnError(e.to_string()))?
-        .args(&args)
-        .spawn()
-        .map_err(|e| SidecarError::SpawnError(e.to_string()))?;
-
-    let pid = child.pid();
-    info!("Sidecar spawned with PID: {}", pid);
-
-    // 保存子进程句柄
-    {
-        let mut guard = SIDECAR_CHILD.lock().await;
-        *guard = Some(child);
-    }
+/// 持续健康多久后清零重启计数
+const HEALTHY_RESET: Duration = Duration::from_secs(60);
+
+/// 日志环形缓冲容量（行）
+const LOG_CAPACITY: usize = 1000;
 
-    // 监听 sidecar 输出
-    let app_handle = app.clone();
+/// 转发外部日志收集端点时复用的单一客户端（连接池随之复用）
+static SINK_CLIENT: once_cell::sync::Lazy<reqwest::Client> =
+    once_cell::sync::Lazy::new(reqwest::Client::new);
+
+/// 串行化外部日志转发：单消费者按入队顺序逐条 POST，保证收集器侧保持顺序。
+static SINK_TX: once_cell::sync::Lazy<
+    tokio::sync::mpsc::UnboundedSender<(String, LogRecord)>,
+> = once_cell::sync::Lazy::new(|| {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<(String, LogRecord)>();
     tauri::async_runtime::spawn(async move {
-        while let Some(event) = rx.recv().await {
-            match event {
-                CommandEvent::Stdout(line) => {
-                    info!("[Backend] {}", line);
+        while let Some((sink, record)) = rx.recv().await {
+            let line = match serde_json::to_string(&record) {
+                Ok(line) => line,
+                Err(_) => continue,
+            };
+            // 换行结尾，便于收集器按 NDJSON 逐行解析
+            let _ = SINK_CLIENT
+                .post(&sink)
+                .header("content-type", "application/x-ndjson")
+                .body(format!("{}\n", line))
+                .send()
+                .await;
+        }
+    });
+    tx
+});
+
+/// 后端输出流
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// 一条后端日志记录
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRecord {
+    /// Unix 时间戳（秒）
+    pub timestamp: u64,
+    /// 级别（stdout 记为 info，stderr 记为 error）
+    pub level: String,
+    /// 来源流
+    pub stream: LogStream,
+    /// 行内容
+    pub message: String,
+}
+
+/// 一个受管理的 sidecar 实例，独占自己的子进程与全部运行期状态。
+pub struct SidecarInstance {
+    /// 实例名（同时作为 sidecar 可执行文件名）
+    name: String,
+    /// 启动配置
+    config: SidecarConfig,
+    /// 子进程句柄
+    child: Mutex<Option<CommandChild>>,
+    /// 当前状态
+    status: SyncMutex<BackendStatus>,
+    /// TCP 端口
+    port: SyncMutex<Option<u16>>,
+    /// Unix 域套接字地址
+    socket: SyncMutex<Option<String>>,
+    /// 最近输出的环形缓冲
+    logs: SyncMutex<VecDeque<LogRecord>>,
+    /// 重启计数
+    restart_attempts: AtomicU32,
+    /// 进程代次：每次 `launch` 自增，用于甄别过期的输出读取任务
+    generation: AtomicU64,
+    /// 是否为预期内的停止/重启
+    intentional_stop: AtomicBool,
+    /// 监视器是否已在运行
+    supervisor_running: AtomicBool,
+    /// 是否把状态镜像进 `AppState`（仅默认实例为真）
+    mirror: bool,
+}
+
+impl SidecarInstance {
+    fn new(name: &str, config: SidecarConfig) -> Self {
+        Self {
+            mirror: name == DEFAULT_SIDECAR,
+            name: name.to_string(),
+            config,
+            child: Mutex::new(None),
+            status: SyncMutex::new(BackendStatus::default()),
+            port: SyncMutex::new(None),
+            socket: SyncMutex::new(None),
+            logs: SyncMutex::new(VecDeque::with_capacity(LOG_CAPACITY)),
+            restart_attempts: AtomicU32::new(0),
+            generation: AtomicU64::new(0),
+            intentional_stop: AtomicBool::new(false),
+            supervisor_running: AtomicBool::new(false),
+        }
+    }
+
+    /// 当前状态快照
+    pub fn status(&self) -> BackendStatus {
+        self.status.lock().clone()
+    }
+
+    /// 统一写入状态/端口/套接字，并在默认实例上镜像到 `AppState`。
+    fn apply_state(
+        &self,
+        app: &AppHandle,
+        status: BackendStatus,
+        port: Option<u16>,
+        socket: Option<String>,
+    ) {
+        *self.status.lock() = status.clone();
+        *self.port.lock() = port;
+        *self.socket.lock() = socket.clone();
+
+        if self.mirror {
+            if let Some(state) = app.try_state::<Arc<AppState>>() {
+                state.set_backend_status(status);
+                state.set_backend_port(port);
+                state.set_backend_socket(socket);
+            }
+        }
+    }
+
+    /// 记录一行输出：写入环形缓冲、发 `backend-log` 事件、按配置转发到外部
+    /// 日志收集端点。
+    fn record_log(&self, app: &AppHandle, stream: LogStream, message: String) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let level = match stream {
+            LogStream::Stdout => "info",
+            LogStream::Stderr => "error",
+        }
+        .to_string();
+
+        let record = LogRecord {
+            timestamp,
+            level,
+            stream,
+            message,
+        };
+
+        {
+            let mut buffer = self.logs.lock();
+            if buffer.len() >= LOG_CAPACITY {
+                buffer.pop_front();
+            }
+            buffer.push_back(record.clone());
+        }
+
+        let _ = app.emit_all("backend-log", &record);
+        self.mirror_to_sink(app, record);
+    }
+
+    /// 返回最近 `tail` 行日志，可按流过滤。
+    fn logs(&self, tail: usize, stream: Option<LogStream>) -> Vec<LogRecord> {
+        let buffer = self.logs.lock();
+        let collected: Vec<LogRecord> = buffer
+            .iter()
+            .filter(|r| stream.map(|s| s == r.stream).unwrap_or(true))
+            .cloned()
+            .collect();
+        let start = collected.len().saturating_sub(tail);
+        collected[start..].to_vec()
+    }
+
+    /// 按 `BackendConfig.log_sink` 把一条记录以 NDJSON 形式 POST 到外部收集器。
+    fn mirror_to_sink(&self, app: &AppHandle, record: LogRecord) {
+        let sink = match app.try_state::<Arc<AppState>>() {
+            Some(state) => state.get_config().backend.log_sink,
+            None => None,
+        };
+        let sink = match sink {
+            Some(sink) if !sink.is_empty() => sink,
+            _ => return,
+        };
+
+        // 入队交给单一消费者按到达顺序发送；发送失败仅意味着通道已关闭。
+        let _ = SINK_TX.send((sink, record));
+    }
+
+    /// 根据当前已记录的传输信息还原健康检查端点。
+    fn current_endpoint(&self) -> Option<health::Endpoint> {
+        if let Some(socket) = self.socket.lock().clone() {
+            let (conn, abstract_ns) = match socket
+                .strip_prefix("\\x00")
+                .or_else(|| socket.strip_prefix('@'))
+            {
+                Some(name) => (name.to_string(), true),
+                None => (socket.clone(), false),
+            };
+            return Some(health::Endpoint::Unix {
+                socket: conn,
+                path: "/api/health".to_string(),
+                abstract_ns,
+            });
+        }
+        self.port
+            .lock()
+            .map(|port| health::Endpoint::Tcp(format!("http://127.0.0.1:{}/api/health", port)))
+    }
+
+    /// 启动实例并在需要时拉起健康监视器。
+    pub async fn start(self: &Arc<Self>, app: &AppHandle) -> Result<u16, SidecarError> {
+        if let BackendStatus::Running { port, .. } = self.status() {
+            info!("Sidecar '{}' already running", self.name);
+            return Ok(port.unwrap_or(0));
+        }
+
+        // 新的一次启动：清掉“预期停止”标记
+        self.intentional_stop.store(false, Ordering::SeqCst);
+
+        let port = self.launch(app).await?;
+        self.clone().spawn_supervisor(app);
+        Ok(port)
+    }
+
+    /// 拉起一个 sidecar 进程并等待其就绪（不负责监视器生命周期）。
+    async fn launch(self: &Arc<Self>, app: &AppHandle) -> Result<u16, SidecarError> {
+        // 进入新的一代：任何更早实例的输出读取任务自此作废，其 `Terminated`
+        // 不得再覆盖本代状态。
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.apply_state(app, BackendStatus::Starting, None, None);
+
+        // 获取数据目录
+        let data_dir = app
+            .path_resolver()
+            .app_data_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+
+        // 按传输方式构建启动参数与健康检查端点
+        let (args, endpoint, socket_desc, port_opt) = match self.config.transport {
+            Transport::Tcp => {
+                let port =
+                    find_available_port(self.config.port_range_start, self.config.port_range_end)
+                        .ok_or(SidecarError::PortError)?;
+                info!("Starting sidecar '{}' on 127.0.0.1:{}", self.name, port);
+                let args = vec![
+                    "--host".to_string(),
+                    "127.0.0.1".to_string(),
+                    "--port".to_string(),
+                    port.to_string(),
+                    "--data-dir".to_string(),
+                    data_dir.to_string_lossy().to_string(),
+                ];
+                let endpoint =
+                    health::Endpoint::Tcp(format!("http://127.0.0.1:{}/api/health", port));
+                (args, endpoint, None, Some(port))
+            }
+            Transport::UnixSocket => {
+                let (arg, conn, abstract_ns) = uds_socket_spec(&self.name, &data_dir);
+                info!("Starting sidecar '{}' on unix socket {}", self.name, arg);
+                let args = vec![
+                    "--uds".to_string(),
+                    arg.clone(),
+                    "--data-dir".to_string(),
+                    data_dir.to_string_lossy().to_string(),
+                ];
+                let endpoint = health::Endpoint::Unix {
+                    socket: conn,
+                    path: "/api/health".to_string(),
+                    abstract_ns,
+                };
+                (args, endpoint, Some(arg), None)
+            }
+        };
+
+        // 启动 sidecar 进程
+        let (mut rx, child) = Command::new_sidecar(&self.name)
+            .map_err(|e| SidecarError::SpawnError(e.to_string()))?
+            .args(&args)
+            .spawn()
+            .map_err(|e| SidecarError::SpawnError(e.to_string()))?;
+
+        let pid = child.pid();
+        info!("Sidecar '{}' spawned with PID: {}", self.name, pid);
+
+        {
+            let mut guard = self.child.lock().await;
+            *guard = Some(child);
+        }
+
+        // 监听 sidecar 输出
+        let inst = self.clone();
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                match event {
+                    CommandEvent::Stdout(line) => {
+                        info!("[{}] {}", inst.name, line);
+                        inst.record_log(&app_handle, LogStream::Stdout, line);
+                    }
+                    CommandEvent::Stderr(line) => {
+                        warn!("[{} STDERR] {}", inst.name, line);
+                        inst.record_log(&app_handle, LogStream::Stderr, line);
+                    }
+                    CommandEvent::Error(err) => {
+                        error!("[{} ERROR] {}", inst.name, err);
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        let code = payload.code.unwrap_or(-1);
+                        // 已进入新的一代：这是上一实例的迟到退出事件，丢弃以免
+                        // 把刚拉起的进程误标为 Stopped 并误发 backend-stopped。
+                        if inst.generation.load(Ordering::SeqCst) != generation {
+                            warn!(
+                                "[{}] Ignoring terminated event (code {}) from stale generation {}",
+                                inst.name, code, generation
+                            );
+                            break;
+                        }
+                        if inst.intentional_stop.load(Ordering::SeqCst) {
+                            warn!("[{}] Process terminated with code: {}", inst.name, code);
+                        } else {
+                            // 意外退出：留给监视器按重启预算处理
+                            error!("[{}] Process exited unexpectedly with code: {}", inst.name, code);
+                        }
+
+                        inst.apply_state(&app_handle, BackendStatus::Stopped, None, None);
+                        let _ = app_handle.emit_all("backend-stopped", code);
+                    }
+                    _ => {}
                 }
-                CommandEvent::Stderr(line) => {
-                    warn!("[Backend STDERR] {}", line);
+            }
+        });
+
+        // 等待后端就绪
+        let ready = timeout(
+            Duration::from_millis(self.config.startup_timeout),
+            wait_for_backend_ready(&endpoint),
+        )
+        .await
+        .map_err(|_| SidecarError::StartupTimeout)?
+        .map_err(|e| SidecarError::HealthCheckFailed(e.to_string()))?;
+
+        if ready {
+            self.apply_state(
+                app,
+                BackendStatus::Running {
+                    port: port_opt,
+                    socket: socket_desc.clone(),
+                    pid: Some(pid),
+                },
+                port_opt,
+                socket_desc,
+            );
+            info!("Sidecar '{}' is ready", self.name);
+            Ok(port_opt.unwrap_or(0))
+        } else {
+            self.apply_state(
+                app,
+                BackendStatus::Failed {
+                    error: "Health check failed".to_string(),
+                },
+                None,
+                None,
+            );
+            Err(SidecarError::HealthCheckFailed(
+                "Backend failed to become ready".to_string(),
+            ))
+        }
+    }
+
+    /// 停止实例。
+    pub async fn stop(&self, app: &AppHandle) -> Result<(), SidecarError> {
+        // 标记为预期内的停止，监视器据此退出、Terminated 不计入重启预算
+        self.intentional_stop.store(true, Ordering::SeqCst);
+        self.apply_state(app, BackendStatus::Stopping, None, None);
+
+        let mut guard = self.child.lock().await;
+        if let Some(child) = guard.take() {
+            info!("Killing sidecar '{}'...", self.name);
+            if let Err(e) = child.kill() {
+                error!("Failed to kill sidecar '{}': {}", self.name, e);
+                return Err(SidecarError::OperationFailed(e.to_string()));
+            }
+        }
+
+        self.apply_state(app, BackendStatus::Stopped, None, None);
+        info!("Sidecar '{}' stopped", self.name);
+        Ok(())
+    }
+
+    /// 重启实例。
+    pub async fn restart(self: &Arc<Self>, app: &AppHandle) -> Result<u16, SidecarError> {
+        info!("Restarting sidecar '{}'...", self.name);
+
+        if let Err(e) = self.stop(app).await {
+            warn!("Error stopping sidecar during restart: {}", e);
+        }
+
+        // 等待一小段时间确保资源释放
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+
+        self.start(app).await
+    }
+
+    /// 监视器内部使用的重启：杀掉当前子进程并重新拉起，不另起监视器。
+    async fn supervised_restart(self: &Arc<Self>, app: &AppHandle) -> Result<u16, SidecarError> {
+        warn!("Supervisor restarting sidecar '{}'...", self.name);
+
+        // 这次 kill 是预期内的，别让 Terminated 再计一笔
+        self.intentional_stop.store(true, Ordering::SeqCst);
+        {
+            let mut guard = self.child.lock().await;
+            if let Some(child) = guard.take() {
+                let _ = child.kill();
+            }
+        }
+        self.apply_state(app, BackendStatus::Stopping, None, None);
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        self.intentional_stop.store(false, Ordering::SeqCst);
+
+        self.launch(app).await
+    }
+
+    /// 启动健康监视器：按 `health_check_interval` 轮询健康状况，持续不健康超过
+    /// `unhealthy_timeout`、或在下一轮轮询发现意外退出时自动重启，重启次数超过
+    /// `max_restart_attempts` 后放弃并置 `Failed`。
+    fn spawn_supervisor(self: Arc<Self>, app: &AppHandle) {
+        // 已有监视器在跑就不再起第二个
+        if self.supervisor_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let interval = Duration::from_millis(self.config.health_check_interval);
+            let unhealthy_timeout = Duration::from_millis(self.config.unhealthy_timeout);
+
+            self.restart_attempts.store(0, Ordering::SeqCst);
+            let mut first_unhealthy: Option<Instant> = None;
+            let mut healthy_since: Option<Instant> = None;
+
+            loop {
+                tokio::time::sleep(interval).await;
+
+                // 判断当前是否需要触发一次重启
+                let needs_restart = match self.status() {
+                    BackendStatus::Running { .. } => {
+                        let endpoint = match self.current_endpoint() {
+                            Some(endpoint) => endpoint,
+                            None => continue,
+                        };
+                        let healthy = health::check_endpoint(&endpoint).await.unwrap_or(false);
+                        if healthy {
+                            first_unhealthy = None;
+                            let since = healthy_since.get_or_insert_with(Instant::now);
+                            // 持续健康一段时间后清零计数，避免偶发抖动耗尽预算
+                            if self.restart_attempts.load(Ordering::SeqCst) > 0
+                                && since.elapsed() >= HEALTHY_RESET
+                            {
+                                info!(
+                                    "Sidecar '{}' healthy for a sustained period; resetting restart counter",
+                                    self.name
+                                );
+                                self.restart_attempts.store(0, Ordering::SeqCst);
+                            }
+                            false
+                        } else {
+                            healthy_since = None;
+                            let since = first_unhealthy.get_or_insert_with(Instant::now);
+                            since.elapsed() >= unhealthy_timeout
+                        }
+                    }
+                    // 意外退出（Terminated 置为 Stopped）也计入重启预算
+                    BackendStatus::Stopped | BackendStatus::Failed { .. } => {
+                        if self.intentional_stop.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        true
+                    }
+                    // 启动中/停止中：等下一轮
+                    _ => false,
+                };
+
+                if !needs_restart {
+                    continue;
                 }
-                CommandEvent::Error(err) => {
-                    error!("[Backend ERROR] {}", err);
+
+                let attempts = self.restart_attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                if attempts > self.config.max_restart_attempts {
+                    error!(
+                        "Sidecar '{}' exceeded max restart attempts ({}); giving up",
+                        self.name, self.config.max_restart_attempts
+                    );
+                    self.apply_state(
+                        &app,
+                        BackendStatus::Failed {
+                            error: "Exceeded maximum restart attempts".to_string(),
+                        },
+                        None,
+                        None,
+                    );
+                    let _ = app.emit_all("backend-gave-up", self.config.max_restart_attempts);
+                    break;
                 }
-                CommandEvent::Terminated(payload) => {
-                    let code = payload.code.unwrap_or(-1);
-                    warn!("[Backend] Process terminated with code: {}", code);
-
-                    // 更新状态
-                    if let Some(state) = app_handle.try_state::<Arc<AppState>>() {
-                        state.set_backend_status(BackendStatus::Stopped);
-                        state.set_backend_port(None);
-                    }
 
-                    // 通知前端
-                    let _ = app_handle.emit_all("backend-stopped", code);
+                // 指数退避：1s, 2s, 4s（封顶）
+                let backoff = BACKOFF_CAP.min(Duration::from_secs(1 << (attempts - 1).min(2)));
+                warn!(
+                    "Sidecar '{}' unhealthy; restart attempt {}/{} after {:?}",
+                    self.name, attempts, self.config.max_restart_attempts, backoff
+                );
+                tokio::time::sleep(backoff).await;
+
+                first_unhealthy = None;
+                healthy_since = None;
+
+                if let Err(e) = self.supervised_restart(&app).await {
+                    error!("Supervised restart failed: {}", e);
                 }
-                _ => {}
             }
-        }
-    });
 
-    // 等待后端就绪
-    let ready = timeout(
-        Duration::from_millis(config.startup_timeout),
-        wait_for_backend_ready(port),
-    )
-    .await
-    .map_err(|_| SidecarError::StartupTimeout)?
-    .map_err(|e| SidecarError::HealthCheckFailed(e.to_string()))?;
-
-    if ready {
-        state.set_backend_status(BackendStatus::Running {
-            port,
-            pid: Some(pid),
+            self.supervisor_running.store(false, Ordering::SeqCst);
         });
-        state.set_backend_port(Some(port));
-        info!("Backend is ready on port {}", port);
-        Ok(port)
-    } else {
-        state.set_backend_status(BackendStatus::Failed {
-            error: "Health check failed".to_string(),
-        });
-        Err(SidecarError::HealthCheckFailed(
-            "Backend failed to become ready".to_string(),
-        ))
+    }
+}
+
+/// 管理全部 sidecar 实例的长生命周期控制器。
+pub struct SidecarController {
+    instances: DashMap<String, Arc<SidecarInstance>>,
+}
+
+impl SidecarController {
+    fn new() -> Self {
+        Self {
+            instances: DashMap::new(),
+        }
+    }
+
+    /// 进程内的全局控制器
+    pub fn global() -> &'static SidecarController {
+        static CONTROLLER: once_cell::sync::Lazy<SidecarController> =
+            once_cell::sync::Lazy::new(SidecarController::new);
+        &CONTROLLER
+    }
+
+    /// 取出指定名称的实例，不存在则以默认配置创建并登记。
+    pub fn get_or_create(&self, name: &str) -> Arc<SidecarInstance> {
+        self.get_or_create_with(name, SidecarConfig::default())
+    }
+
+    /// 取出指定名称的实例，不存在则以给定配置创建并登记。
+    ///
+    /// 已登记的实例其配置在创建时固定，这里的 `config` 仅用于首次创建。
+    pub fn get_or_create_with(&self, name: &str, config: SidecarConfig) -> Arc<SidecarInstance> {
+        self.instances
+            .entry(name.to_string())
+            .or_insert_with(|| Arc::new(SidecarInstance::new(name, config)))
+            .clone()
+    }
+
+    /// 查询实例（未登记则返回 None）。
+    pub fn get(&self, name: &str) -> Option<Arc<SidecarInstance>> {
+        self.instances.get(name).map(|entry| entry.clone())
+    }
+
+    /// 启动指定实例。
+    pub async fn start(&self, app: &AppHandle, name: &str) -> Result<u16, SidecarError> {
+        self.get_or_create(name).start(app).await
+    }
+
+    /// 停止指定实例。
+    pub async fn stop(&self, app: &AppHandle, name: &str) -> Result<(), SidecarError> {
+        match self.get(name) {
+            Some(inst) => inst.stop(app).await,
+            None => Err(SidecarError::NotRunning),
+        }
+    }
+
+    /// 重启指定实例。
+    pub async fn restart(&self, app: &AppHandle, name: &str) -> Result<u16, SidecarError> {
+        self.get_or_create(name).restart(app).await
+    }
+
+    /// 查询指定实例状态。
+    pub fn status(&self, name: &str) -> BackendStatus {
+        self.get(name)
+            .map(|inst| inst.status())
+            .unwrap_or_default()
+    }
+
+    /// 优雅关闭：逐个杀掉注册表中的所有子进程。
+    pub async fn shutdown_all(&self, app: &AppHandle) {
+        let names: Vec<String> = self.instances.iter().map(|e| e.key().clone()).collect();
+        for name in names {
+            if let Some(inst) = self.get(&name) {
+                let _ = inst.stop(app).await;
+            }
+        }
+    }
+}
+
+/// 为 UDS 传输计算 `(--uds 参数, 连接用地址, 是否抽象命名空间)`。
+///
+/// Linux 上优先用抽象命名空间套接字（路径以 NUL 开头，转义为 `\x00name`），
+/// 崩溃后无需清理文件系统条目；其它平台退回到数据目录下的套接字文件。
+fn uds_socket_spec(name: &str, data_dir: &std::path::Path) -> (String, String, bool) {
+    #[cfg(target_os = "linux")]
+    {
+        let _ = data_dir;
+        (format!("\\x00{}", name), name.to_string(), true)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let path = data_dir
+            .join(format!("{}.sock", name))
+            .to_string_lossy()
+            .to_string();
+        (path.clone(), path, false)
     }
 }
 
 /// 等待后端就绪
-async fn wait_for_backend_ready(port: u16) -> Result<bool, String> {
-    let url = format!("http://127.0.0.1:{}/api/health", port);
+async fn wait_for_backend_ready(endpoint: &health::Endpoint) -> Result<bool, String> {
     let max_attempts = 60; // 最多尝试 60 次
     let interval = Duration::from_millis(500);
 
     for attempt in 1..=max_attempts {
-        match health::check_health(&url).await {
+        match health::check_endpoint(endpoint).await {
             Ok(true) => {
                 info!("Backend health check passed on attempt {}", attempt);
                 return Ok(true);
@@ -197,11 +747,7 @@ async fn wait_for_backend_ready(port: u16) -> Result<bool, String> {
             }
             Err(e) => {
                 if attempt % 10 == 0 {
-                    info!(
-                        "Backend not ready yet (attempt {}): {}",
-                        attempt,
-                        e
-                    );
+                    info!("Backend not ready yet (attempt {}): {}", attempt, e);
                 }
             }
         }
@@ -211,52 +757,91 @@ async fn wait_for_backend_ready(port: u16) -> Result<bool, String> {
     Err("Backend failed to become ready within timeout".to_string())
 }
 
-/// 停止后端服务
-pub async fn stop_backend(app: &AppHandle) -> Result<(), SidecarError> {
-    let state = app.state::<Arc<AppState>>();
-
-    // 更新状态
-    state.set_backend_status(BackendStatus::Stopping);
-
-    // 终止子进程
-    let mut guard = SIDECAR_CHILD.lock().await;
-    if let Some(child) = guard.take() {
-        info!("Killing sidecar process...");
-        if let Err(e) = child.kill() {
-            error!("Failed to kill sidecar: {}", e);
-            return Err(SidecarError::OperationFailed(e.to_string()));
-        }
+// --- 默认 `"okcvm-server"` 实例的薄包装，保持既有调用点不变 --------------
+
+/// 按 `BackendConfig` 派生默认实例的 sidecar 配置（当前仅挑选传输方式）。
+fn default_sidecar_config(app: &AppHandle) -> SidecarConfig {
+    let mut config = SidecarConfig::default();
+    if let Some(state) = app.try_state::<Arc<AppState>>() {
+        config.transport = Transport::from_config(&state.get_config().backend.transport);
     }
+    config
+}
 
-    state.set_backend_status(BackendStatus::Stopped);
-    state.set_backend_port(None);
+/// 启动默认后端服务
+pub async fn start_backend(app: &AppHandle) -> Result<u16, SidecarError> {
+    // 首次创建时按用户配置选择 TCP / UDS 传输
+    SidecarController::global()
+        .get_or_create_with(DEFAULT_SIDECAR, default_sidecar_config(app))
+        .start(app)
+        .await
+}
 
-    info!("Backend stopped");
-    Ok(())
+/// 停止默认后端服务
+pub async fn stop_backend(app: &AppHandle) -> Result<(), SidecarError> {
+    SidecarController::global().stop(app, DEFAULT_SIDECAR).await
 }
 
-/// 重启后端服务
+/// 重启默认后端服务
 pub async fn restart_backend(app: &AppHandle) -> Result<u16, SidecarError> {
-    info!("Restarting backend...");
-
-    // 先停止
-    if let Err(e) = stop_backend(app).await {
-        warn!("Error stopping backend during restart: {}", e);
-    }
-
-    // 等待一小段时间确保资源释放
-    tokio::time::sleep(Duration::from_millis(1000)).await;
+    SidecarController::global()
+        .restart(app, DEFAULT_SIDECAR)
+        .await
+}
 
-    // 重新启动
-    start_backend(app).await
+/// 关闭所有受管理的 sidecar 实例
+pub async fn shutdown_all(app: &AppHandle) {
+    SidecarController::global().shutdown_all(app).await;
 }
 
-/// 获取后端状态
+/// 获取默认后端状态
 pub fn get_backend_status(app: &AppHandle) -> BackendStatus {
     app.state::<Arc<AppState>>().get_backend_status()
 }
 
-/// 获取后端 URL
+/// 获取默认后端 URL
 pub fn get_backend_url(app: &AppHandle) -> Option<String> {
     app.state::<Arc<AppState>>().get_backend_url()
 }
+
+/// 返回默认后端最近 `tail` 行日志，可按流过滤。
+pub fn get_logs(tail: usize, stream: Option<LogStream>) -> Vec<LogRecord> {
+    SidecarController::global()
+        .get(DEFAULT_SIDECAR)
+        .map(|inst| inst.logs(tail, stream))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_from_config_selects_unix_socket() {
+        assert_eq!(Transport::from_config("unix"), Transport::UnixSocket);
+        assert_eq!(Transport::from_config("UDS"), Transport::UnixSocket);
+        assert_eq!(Transport::from_config("tcp"), Transport::Tcp);
+        assert_eq!(Transport::from_config("nonsense"), Transport::Tcp);
+    }
+
+    #[test]
+    fn uds_socket_spec_drives_unix_socket_branch() {
+        let dir = std::path::Path::new("/tmp/okc-test");
+        let (arg, conn, abstract_ns) = uds_socket_spec(DEFAULT_SIDECAR, dir);
+
+        #[cfg(target_os = "linux")]
+        {
+            // Linux 走抽象命名空间：`\x00name`，连接名不带前缀
+            assert_eq!(arg, format!("\\x00{}", DEFAULT_SIDECAR));
+            assert_eq!(conn, DEFAULT_SIDECAR);
+            assert!(abstract_ns);
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            // 其它平台退回数据目录下的套接字文件
+            assert!(arg.ends_with(&format!("{}.sock", DEFAULT_SIDECAR)));
+            assert_eq!(arg, conn);
+            assert!(!abstract_ns);
+        }
+    }
+}
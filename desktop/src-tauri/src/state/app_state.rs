@@ -1,8 +1,18 @@
 //! Global Application State
 
+use crate::utils::paths;
+use log::{info, warn};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 
+/// 配置文件的当前 schema 版本。每次改变序列化布局（例如新增
+/// `ShortcutsConfig` 字段）时递增，并在 `migrate_config` 中补一步迁移。
+pub const CONFIG_VERSION: u32 = 1;
+
+fn default_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
 /// 后端服务状态
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "status", rename_all = "snake_case")]
@@ -11,7 +21,12 @@ pub enum BackendStatus {
     Starting,
     /// 运行中
     Running {
-        port: u16,
+        /// TCP 传输时的端口
+        #[serde(skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+        /// Unix 域套接字传输时的地址
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        socket: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pid: Option<u32>,
     },
@@ -29,9 +44,42 @@ impl Default for BackendStatus {
     }
 }
 
+/// 自更新状态（风格与 [`BackendStatus`] 一致）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UpdateStatus {
+    /// 尚未检查
+    Idle,
+    /// 正在检查
+    Checking,
+    /// 已是最新
+    UpToDate,
+    /// 有可用更新
+    Available {
+        version: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        notes: Option<String>,
+    },
+    /// 正在下载（0-100）
+    Downloading { progress: u8 },
+    /// 已下载完成，等待安装/重启
+    Ready { version: String },
+    /// 检查或下载失败
+    Failed { error: String },
+}
+
+impl Default for UpdateStatus {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
 /// 应用配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
+    /// 配置 schema 版本（用于向后兼容的迁移）
+    #[serde(default = "default_config_version")]
+    pub version: u32,
     /// 窗口配置
     pub window: WindowConfig,
     /// 外观配置
@@ -47,6 +95,7 @@ pub struct AppConfig {
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             window: WindowConfig::default(),
             appearance: AppearanceConfig::default(),
             shortcuts: ShortcutsConfig::default(),
@@ -115,6 +164,17 @@ pub struct BackendConfig {
     pub auto_start: bool,
     /// 固定端口（None 表示自动分配）
     pub port: Option<u16>,
+    /// 外部日志收集端点（None 表示不转发）；后端日志会以 NDJSON 形式 POST 过去
+    #[serde(default)]
+    pub log_sink: Option<String>,
+    /// 后端传输方式: "tcp"（默认）或 "unix"（Unix 域套接字）
+    #[serde(default = "default_transport")]
+    pub transport: String,
+}
+
+/// `transport` 字段的默认值
+fn default_transport() -> String {
+    "tcp".to_string()
 }
 
 impl Default for BackendConfig {
@@ -122,6 +182,8 @@ impl Default for BackendConfig {
         Self {
             auto_start: true,
             port: None,
+            log_sink: None,
+            transport: default_transport(),
         }
     }
 }
@@ -143,23 +205,107 @@ impl Default for UpdatesConfig {
     }
 }
 
+/// 把旧版本的序列化布局升级到当前 schema。
+///
+/// 目前只有 v1，所以这里仅补齐缺失的 `version` 字段；未来新增字段时在此
+/// 按 `version` 依次打补丁即可，缺省值来自各子配置的 `Default`。
+fn migrate_config(mut value: serde_json::Value) -> serde_json::Value {
+    let from = value
+        .get("version")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as u32;
+
+    // 未知或缺失版本（0）视为最初的无版本布局，逐级升级。
+    if from < CONFIG_VERSION {
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(
+                "version".to_string(),
+                serde_json::Value::from(CONFIG_VERSION),
+            );
+        }
+    }
+
+    if from > 0 && from != CONFIG_VERSION {
+        info!("Migrated config from version {} to {}", from, CONFIG_VERSION);
+    }
+
+    value
+}
+
+/// 从磁盘加载配置，文件缺失或损坏时回退到默认值。
+pub fn load_config() -> AppConfig {
+    let path = match paths::get_app_config_file() {
+        Some(p) => p,
+        None => return AppConfig::default(),
+    };
+
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(_) => return AppConfig::default(),
+    };
+
+    let value: serde_json::Value = match serde_json::from_str(&raw) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!("Config file is corrupt, falling back to defaults: {}", e);
+            return AppConfig::default();
+        }
+    };
+
+    match serde_json::from_value(migrate_config(value)) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("Config file is incompatible, falling back to defaults: {}", e);
+            AppConfig::default()
+        }
+    }
+}
+
+/// 原子地把配置写回磁盘：先写入 `config.json.tmp`，再 `rename` 覆盖，
+/// 这样即便写入中途崩溃也不会把已有配置截断成半个文件。
+pub fn save_config(config: &AppConfig) -> std::io::Result<()> {
+    let path = paths::get_app_config_file().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "config directory is unavailable",
+        )
+    })?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    let serialized = serde_json::to_string_pretty(config)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(&tmp, serialized)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
 /// 全局应用状态
 pub struct AppState {
     /// 后端状态
     backend_status: RwLock<BackendStatus>,
-    /// 后端端口
+    /// 后端端口（TCP 传输）
     backend_port: RwLock<Option<u16>>,
+    /// 后端 Unix 域套接字地址（UDS 传输）
+    backend_socket: RwLock<Option<String>>,
     /// 应用配置
     config: RwLock<AppConfig>,
+    /// 自更新状态
+    update_status: RwLock<UpdateStatus>,
 }
 
 impl AppState {
-    /// 创建新的应用状态
+    /// 创建新的应用状态，并从磁盘加载已持久化的配置（缺失则用默认值）。
     pub fn new() -> Self {
         Self {
             backend_status: RwLock::new(BackendStatus::default()),
             backend_port: RwLock::new(None),
-            config: RwLock::new(AppConfig::default()),
+            backend_socket: RwLock::new(None),
+            config: RwLock::new(load_config()),
+            update_status: RwLock::new(UpdateStatus::default()),
         }
     }
 
@@ -183,8 +329,23 @@ impl AppState {
         *self.backend_port.write() = port;
     }
 
+    /// 获取后端 Unix 域套接字地址
+    pub fn get_backend_socket(&self) -> Option<String> {
+        self.backend_socket.read().clone()
+    }
+
+    /// 设置后端 Unix 域套接字地址
+    pub fn set_backend_socket(&self, socket: Option<String>) {
+        *self.backend_socket.write() = socket;
+    }
+
     /// 获取后端 URL
+    ///
+    /// UDS 传输下返回 `http+unix://<socket>` 形式的地址，其余走 TCP 回环。
     pub fn get_backend_url(&self) -> Option<String> {
+        if let Some(socket) = self.backend_socket.read().clone() {
+            return Some(format!("http+unix://{}", socket));
+        }
         self.backend_port
             .read()
             .map(|port| format!("http://127.0.0.1:{}", port))
@@ -208,6 +369,21 @@ impl AppState {
         let mut config = self.config.write();
         f(&mut config);
     }
+
+    /// 把当前配置原子地持久化到磁盘。
+    pub fn persist_config(&self) -> std::io::Result<()> {
+        save_config(&self.config.read())
+    }
+
+    /// 获取自更新状态
+    pub fn get_update_status(&self) -> UpdateStatus {
+        self.update_status.read().clone()
+    }
+
+    /// 设置自更新状态
+    pub fn set_update_status(&self, status: UpdateStatus) {
+        *self.update_status.write() = status;
+    }
 }
 
 impl Default for AppState {
@@ -231,15 +407,38 @@ mod tests {
     fn test_backend_status_update() {
         let state = AppState::new();
         state.set_backend_status(BackendStatus::Running {
-            port: 8080,
+            port: Some(8080),
+            socket: None,
             pid: Some(1234),
         });
         assert!(matches!(
             state.get_backend_status(),
-            BackendStatus::Running { port: 8080, .. }
+            BackendStatus::Running {
+                port: Some(8080),
+                ..
+            }
         ));
     }
 
+    #[test]
+    fn test_migrate_config_adds_version() {
+        // 模拟旧版本（无 version 字段）的最小配置
+        let legacy = serde_json::json!({
+            "window": { "width": 800, "height": 600, "x": null, "y": null, "maximized": false },
+            "appearance": { "theme": "dark", "font_size": 16 },
+            "shortcuts": { "toggle_window": "CmdOrCtrl+Shift+K", "new_chat": "CmdOrCtrl+Shift+N" },
+            "backend": { "auto_start": true, "port": null },
+            "updates": { "auto_check": true, "channel": "stable" }
+        });
+
+        let migrated = migrate_config(legacy);
+        assert_eq!(migrated["version"], serde_json::json!(CONFIG_VERSION));
+
+        let config: AppConfig = serde_json::from_value(migrated).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.appearance.theme, "dark");
+    }
+
     #[test]
     fn test_backend_url() {
         let state = AppState::new();
@@ -251,4 +450,15 @@ mod tests {
             Some("http://127.0.0.1:8080".to_string())
         );
     }
+
+    #[test]
+    fn test_backend_url_prefers_socket() {
+        let state = AppState::new();
+        state.set_backend_port(Some(8080));
+        state.set_backend_socket(Some("/tmp/okcvm.sock".to_string()));
+        assert_eq!(
+            state.get_backend_url(),
+            Some("http+unix:///tmp/okcvm.sock".to_string())
+        );
+    }
 }
@@ -1,34 +1,139 @@
 //! Global Shortcuts Management
 
+use crate::state::{AppState, ShortcutsConfig};
 use log::{error, info};
+use std::sync::Arc;
 use tauri::{AppHandle, GlobalShortcutManager, Manager};
+use thiserror::Error;
 
-/// 注册全局快捷键
-pub fn register_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+/// 快捷键处理错误
+#[derive(Debug, Error)]
+pub enum ShortcutError {
+    /// 加速键字符串无法解析
+    #[error("invalid accelerator '{0}'")]
+    Invalid(String),
+
+    /// 该组合键已被系统或其它应用占用
+    #[error("accelerator '{0}' is already in use by the system or another application")]
+    Conflict(String),
+
+    /// 底层注册接口返回的其它错误
+    #[error("shortcut registration failed: {0}")]
+    Backend(String),
+}
+
+/// 注册全局快捷键（使用当前 `ShortcutsConfig` 中的用户配置）
+pub fn register_shortcuts(app: &AppHandle) -> Result<(), ShortcutError> {
+    let shortcuts = app.state::<Arc<AppState>>().get_config().shortcuts;
+    apply_shortcuts(app, &shortcuts)
+}
+
+/// 按给定配置重新注册所有全局快捷键。
+///
+/// 任一绑定无效或被占用都会返回类型化错误，便于 `set_app_config` 据此拒绝这次
+/// 修改；此时会把注册回滚到调用前的那套绑定，绝不停在“旧绑定已清空、新绑定只
+/// 注册了一半”的坏状态。
+pub fn apply_shortcuts(app: &AppHandle, shortcuts: &ShortcutsConfig) -> Result<(), ShortcutError> {
+    // 快照当前生效的配置，失败时据此回滚。
+    let previous = app.state::<Arc<AppState>>().get_config().shortcuts;
+
+    match register_all(app, shortcuts) {
+        Ok(()) => {
+            info!("Global shortcuts registered successfully");
+            Ok(())
+        }
+        Err(e) => {
+            // 回滚到上一套可用绑定，避免用户“静默丢失一个快捷键”。
+            if let Err(rollback) = register_all(app, &previous) {
+                error!("Failed to roll back shortcuts after error: {}", rollback);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// 清空并按给定配置重新注册全部绑定；任一步失败即带错误返回，调用方负责回滚。
+fn register_all(app: &AppHandle, shortcuts: &ShortcutsConfig) -> Result<(), ShortcutError> {
     let mut manager = app.global_shortcut_manager();
+    manager
+        .unregister_all()
+        .map_err(|e| ShortcutError::Backend(e.to_string()))?;
 
-    // 显示/隐藏窗口 - Cmd/Ctrl+Shift+K
+    // 显示/隐藏窗口
     let app_handle = app.clone();
-    manager.register("CmdOrCtrl+Shift+K", move || {
+    register_one(app, &shortcuts.toggle_window, move || {
         info!("Global shortcut triggered: toggle window");
         toggle_window_visibility(&app_handle);
     })?;
 
-    // 新建对话 - Cmd/Ctrl+Shift+N
+    // 新建对话
     let app_handle = app.clone();
-    manager.register("CmdOrCtrl+Shift+N", move || {
+    register_one(app, &shortcuts.new_chat, move || {
         info!("Global shortcut triggered: new chat");
         create_new_chat(&app_handle);
     })?;
 
-    info!("Global shortcuts registered successfully");
     Ok(())
 }
 
+/// 校验一个加速键字符串：解析失败或已被占用时返回类型化错误。
+///
+/// 不改变既有注册状态——临时注册再立即注销，仅用于让偏好设置 UI 提前
+/// 检测冲突。
+pub fn validate_shortcut(app: &AppHandle, accelerator: &str) -> Result<(), ShortcutError> {
+    let mut manager = app.global_shortcut_manager();
+
+    if manager
+        .is_registered(accelerator)
+        .map_err(|e| ShortcutError::Backend(e.to_string()))?
+    {
+        return Err(ShortcutError::Conflict(accelerator.to_string()));
+    }
+
+    manager
+        .register(accelerator, || {})
+        .map_err(|e| classify(accelerator, e.to_string()))?;
+    let _ = manager.unregister(accelerator);
+    Ok(())
+}
+
+/// 注册单个加速键并把失败映射为类型化错误
+fn register_one<F>(app: &AppHandle, accelerator: &str, handler: F) -> Result<(), ShortcutError>
+where
+    F: Fn() + Send + 'static,
+{
+    let mut manager = app.global_shortcut_manager();
+
+    if manager
+        .is_registered(accelerator)
+        .map_err(|e| ShortcutError::Backend(e.to_string()))?
+    {
+        return Err(ShortcutError::Conflict(accelerator.to_string()));
+    }
+
+    manager
+        .register(accelerator, handler)
+        .map_err(|e| classify(accelerator, e.to_string()))
+}
+
+/// 把底层错误按文案分类为解析错误或占用冲突
+fn classify(accelerator: &str, message: String) -> ShortcutError {
+    let lower = message.to_lowercase();
+    if lower.contains("already") || lower.contains("registered") || lower.contains("in use") {
+        ShortcutError::Conflict(accelerator.to_string())
+    } else if lower.contains("parse") || lower.contains("invalid") || lower.contains("unknown") {
+        ShortcutError::Invalid(accelerator.to_string())
+    } else {
+        ShortcutError::Backend(message)
+    }
+}
+
 /// 注销所有快捷键
-pub fn unregister_shortcuts(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+pub fn unregister_shortcuts(app: &AppHandle) -> Result<(), ShortcutError> {
     let mut manager = app.global_shortcut_manager();
-    manager.unregister_all()?;
+    manager
+        .unregister_all()
+        .map_err(|e| ShortcutError::Backend(e.to_string()))?;
     info!("Global shortcuts unregistered");
     Ok(())
 }
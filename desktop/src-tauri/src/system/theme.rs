@@ -1,6 +1,9 @@
 //! System Theme Detection
 
+use crate::state::AppState;
 use log::info;
+use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 /// 主题类型
@@ -88,3 +91,201 @@ pub fn notify_theme_change(app: &AppHandle, theme: Theme) {
 pub fn get_system_theme_cmd() -> String {
     get_system_theme().as_str().to_string()
 }
+
+/// 采样式监视路径（macOS 及各平台原生路径不可用时的回退）的主题轮询间隔
+const WATCH_INTERVAL: Duration = Duration::from_millis(2000);
+
+/// 启动后台主题监视器，在运行期间检测系统亮/暗切换并驱动 `theme-changed`。
+///
+/// Windows 与 Linux 用原生事件源，拿不到时才退回轮询：Windows 阻塞在
+/// `RegNotifyChangeKeyValue(HKCU\...\Themes\Personalize)` 上，Linux 监听
+/// `org.freedesktop.appearance` 的 `color-scheme`（经 portal `SettingChanged`
+/// 信号，与 `commands/files.rs` 一样走 D-Bus 命令行工具）。macOS 不订阅
+/// `AppleInterfaceThemeChangedNotification`，始终按 [`WATCH_INTERVAL`] 采样
+/// [`get_system_theme`]。任何路径都只在真正发生跳变时
+/// 才发事件（去抖），并且当用户把 `AppearanceConfig.theme` 钉死为具体主题
+/// （非 `"system"`）时抑制发送。
+pub fn start_theme_watcher(app: &AppHandle) {
+    let app = app.clone();
+
+    #[cfg(target_os = "windows")]
+    watch_windows(app);
+
+    #[cfg(target_os = "linux")]
+    watch_linux(app);
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    watch_polling(app);
+}
+
+/// 若主题较上次发生跳变且外观仍跟随系统，则发出 `theme-changed`。
+fn emit_if_changed(app: &AppHandle, last: &mut Theme) {
+    let current = get_system_theme();
+    if current == *last {
+        return;
+    }
+    *last = current;
+
+    // 用户钉死了固定主题时，系统翻转不应影响前端。
+    if !follows_system(app) {
+        return;
+    }
+    notify_theme_change(app, current);
+}
+
+/// 采样式监视器：macOS 及未知平台的回退路径。
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn watch_polling(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last = get_system_theme();
+        loop {
+            std::thread::sleep(WATCH_INTERVAL);
+            emit_if_changed(&app, &mut last);
+        }
+    });
+}
+
+/// Windows：阻塞在 `Personalize` 键的变更通知上，收到即重新读取主题。
+#[cfg(target_os = "windows")]
+fn watch_windows(app: AppHandle) {
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use std::ptr;
+
+    // 直接绑定 advapi32（std 已链接），避免为一个回调引入额外依赖。
+    type Hkey = *mut c_void;
+    const HKEY_CURRENT_USER: Hkey = 0x8000_0001u32 as usize as Hkey;
+    const KEY_NOTIFY: u32 = 0x0010;
+    const REG_NOTIFY_CHANGE_LAST_SET: u32 = 0x0000_0004;
+    const ERROR_SUCCESS: i32 = 0;
+
+    #[link(name = "advapi32")]
+    extern "system" {
+        fn RegOpenKeyExW(
+            h_key: Hkey,
+            lp_sub_key: *const u16,
+            ul_options: u32,
+            sam_desired: u32,
+            phk_result: *mut Hkey,
+        ) -> i32;
+        fn RegNotifyChangeKeyValue(
+            h_key: Hkey,
+            b_watch_subtree: i32,
+            dw_notify_filter: u32,
+            h_event: *mut c_void,
+            f_asynchronous: i32,
+        ) -> i32;
+        fn RegCloseKey(h_key: Hkey) -> i32;
+    }
+
+    std::thread::spawn(move || {
+        let sub_key: Vec<u16> = std::ffi::OsStr::new(
+            "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        )
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+        let mut key: Hkey = ptr::null_mut();
+        // SAFETY: 传入合法的以 NUL 结尾的宽字符串与输出指针。
+        let opened = unsafe {
+            RegOpenKeyExW(
+                HKEY_CURRENT_USER,
+                sub_key.as_ptr(),
+                0,
+                KEY_NOTIFY,
+                &mut key,
+            )
+        };
+        if opened != ERROR_SUCCESS {
+            // 打不开键时退回采样，保证功能不丢。
+            return watch_polling_blocking(app);
+        }
+
+        let mut last = get_system_theme();
+        loop {
+            // 同步阻塞直到该键下任意值被写入。
+            // SAFETY: `key` 由上面成功打开且全程有效。
+            let rc = unsafe {
+                RegNotifyChangeKeyValue(key, 0, REG_NOTIFY_CHANGE_LAST_SET, ptr::null_mut(), 0)
+            };
+            if rc != ERROR_SUCCESS {
+                break;
+            }
+            emit_if_changed(&app, &mut last);
+        }
+
+        // SAFETY: `key` 仅在此处关闭一次。
+        unsafe {
+            RegCloseKey(key);
+        }
+    });
+}
+
+/// Windows 原生路径不可用时的内联采样回退。
+#[cfg(target_os = "windows")]
+fn watch_polling_blocking(app: AppHandle) {
+    let mut last = get_system_theme();
+    loop {
+        std::thread::sleep(WATCH_INTERVAL);
+        emit_if_changed(&app, &mut last);
+    }
+}
+
+/// Linux：监听 portal 的 `SettingChanged` 信号，`color-scheme` 变更即重读主题。
+#[cfg(target_os = "linux")]
+fn watch_linux(app: AppHandle) {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    std::thread::spawn(move || {
+        let child = Command::new("dbus-monitor")
+            .args([
+                "--session",
+                "type='signal',interface='org.freedesktop.portal.Settings',member='SettingChanged'",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            // 没有 dbus-monitor（或无会话总线）时退回采样。
+            Err(_) => return watch_polling_blocking(app),
+        };
+
+        let stdout = match child.stdout.take() {
+            Some(stdout) => stdout,
+            None => return watch_polling_blocking(app),
+        };
+
+        let mut last = get_system_theme();
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            // 只有外观命名空间的 color-scheme 键变更才需要重新判定。
+            if line.contains("color-scheme") {
+                emit_if_changed(&app, &mut last);
+            }
+        }
+
+        // dbus-monitor 退出（总线断开等）后退回采样，避免监视器静默失效。
+        let _ = child.wait();
+        watch_polling_blocking(app);
+    });
+}
+
+/// Linux 原生路径不可用时的内联采样回退。
+#[cfg(target_os = "linux")]
+fn watch_polling_blocking(app: AppHandle) {
+    let mut last = get_system_theme();
+    loop {
+        std::thread::sleep(WATCH_INTERVAL);
+        emit_if_changed(&app, &mut last);
+    }
+}
+
+/// 当前外观配置是否跟随系统主题
+fn follows_system(app: &AppHandle) -> bool {
+    app.try_state::<Arc<AppState>>()
+        .map(|state| state.get_config().appearance.theme == "system")
+        .unwrap_or(true)
+}
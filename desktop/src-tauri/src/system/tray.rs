@@ -1,6 +1,8 @@
 //! System Tray Management
 
+use crate::state::{AppState, UpdateStatus};
 use log::info;
+use std::sync::Arc;
 use tauri::{
     AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
     SystemTrayMenuItem,
@@ -17,6 +19,7 @@ pub fn create_tray() -> SystemTray {
         .add_item(CustomMenuItem::new("restart", "重启后端服务"))
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(CustomMenuItem::new("preferences", "偏好设置..."))
+        .add_item(CustomMenuItem::new("check_update", "检查更新..."))
         .add_item(CustomMenuItem::new("about", "关于 OKCVM"))
         .add_native_item(SystemTrayMenuItem::Separator)
         .add_item(CustomMenuItem::new("quit", "退出"));
@@ -42,6 +45,7 @@ pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
                 "new_chat" => create_new_chat(app),
                 "restart" => restart_backend(app),
                 "preferences" => open_preferences(app),
+                "check_update" => check_for_update(app),
                 "about" => show_about(app),
                 "quit" => quit_app(app),
                 _ => {}
@@ -81,15 +85,58 @@ fn open_preferences(app: &AppHandle) {
     let _ = app.emit_all("open-preferences", ());
 }
 
+/// 触发一次更新检查，并把结果回显给用户
+fn check_for_update(app: &AppHandle) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match crate::updater::check_for_update(app_handle.clone()).await {
+            Ok(UpdateStatus::Available { version, .. }) => {
+                let _ = tauri::api::dialog::message(
+                    app_handle.get_window("main").as_ref(),
+                    "检查更新",
+                    format!("发现新版本 {}，可在应用内下载更新。", version),
+                );
+            }
+            Ok(UpdateStatus::UpToDate) => {
+                let _ = tauri::api::dialog::message(
+                    app_handle.get_window("main").as_ref(),
+                    "检查更新",
+                    "当前已是最新版本。",
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let _ = tauri::api::dialog::message(
+                    app_handle.get_window("main").as_ref(),
+                    "检查更新",
+                    format!("检查更新失败: {}", e),
+                );
+            }
+        }
+    });
+}
+
 /// 显示关于
 fn show_about(app: &AppHandle) {
     let version = app.package_info().version.to_string();
+
+    // “关于 OKCVM” 区域顺带展示是否有待安装的更新。
+    let update_line = match app.try_state::<Arc<AppState>>().map(|s| s.get_update_status()) {
+        Some(UpdateStatus::Available { version, .. }) => {
+            format!("\n\n有可用更新: {}", version)
+        }
+        Some(UpdateStatus::Ready { version }) => {
+            format!("\n\n更新已下载（{}）", version)
+        }
+        _ => String::new(),
+    };
+
     let _ = tauri::api::dialog::message(
         app.get_window("main").as_ref(),
         "关于 OKCVM",
         format!(
-            "OKCVM Desktop\n\n版本: {}\n\nOK Computer in a Box: Your Self-Hosted Agent Workflow Layer",
-            version
+            "OKCVM Desktop\n\n版本: {}\n\nOK Computer in a Box: Your Self-Hosted Agent Workflow Layer{}",
+            version, update_line
         ),
     );
 }
@@ -98,10 +145,10 @@ fn show_about(app: &AppHandle) {
 fn quit_app(app: &AppHandle) {
     info!("Quitting application...");
 
-    // 停止后端
+    // 停止全部受管理的 sidecar
     let app_handle = app.clone();
     tauri::async_runtime::spawn(async move {
-        let _ = crate::sidecar::manager::stop_backend(&app_handle).await;
+        crate::sidecar::manager::shutdown_all(&app_handle).await;
     });
 
     // 退出
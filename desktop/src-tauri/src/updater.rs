@@ -0,0 +1,259 @@
+//! Self-Update Subsystem
+//!
+//! Checks a channel-specific release manifest, compares the advertised
+//! version against the running build and, on request, downloads the new
+//! artifact while streaming progress to the frontend and the system tray.
+
+use crate::state::{AppState, UpdateStatus};
+use log::{error, info, warn};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+
+/// 发布清单托管的根地址；实际 URL 由更新渠道拼出。
+const MANIFEST_BASE: &str = "https://releases.okcvm.app";
+
+/// 周期性自动检查的间隔（6 小时）
+const AUTO_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 更新子系统错误
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    #[error("failed to fetch release manifest: {0}")]
+    Fetch(String),
+
+    #[error("release manifest is malformed: {0}")]
+    Manifest(String),
+
+    #[error("no update is available to install")]
+    NothingToInstall,
+
+    #[error("failed to download update: {0}")]
+    Download(String),
+}
+
+/// 渠道发布清单
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseManifest {
+    /// 最新版本号（semver）
+    pub version: String,
+    /// 下载地址
+    pub url: String,
+    /// 更新说明
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// 按更新渠道拼出清单地址
+fn manifest_url(channel: &str) -> String {
+    // 非法渠道回退到 stable，避免拼出意外路径。
+    let channel = match channel {
+        "beta" => "beta",
+        _ => "stable",
+    };
+    format!("{}/{}/latest.json", MANIFEST_BASE, channel)
+}
+
+/// 比较两个 semver（按 `.`、`-`、`+` 分段逐段做数值比较，非数字段记为 0），
+/// `candidate` 比 `current` 新时返回 true。
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['.', '-', '+'])
+            .map(|part| part.parse::<u64>().unwrap_or(0))
+            .collect()
+    };
+
+    let (a, b) = (parse(candidate), parse(current));
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        if x != y {
+            return x > y;
+        }
+    }
+    false
+}
+
+/// 拉取并解析当前渠道的发布清单
+async fn fetch_manifest(channel: &str) -> Result<ReleaseManifest, UpdateError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| UpdateError::Fetch(e.to_string()))?;
+
+    let url = manifest_url(channel);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| UpdateError::Fetch(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(UpdateError::Fetch(format!("HTTP {}", response.status())));
+    }
+
+    response
+        .json::<ReleaseManifest>()
+        .await
+        .map_err(|e| UpdateError::Manifest(e.to_string()))
+}
+
+/// 检查是否有可用更新，更新 `AppState` 并在有新版本时发 `update-available`。
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateStatus, String> {
+    check_internal(&app).await.map_err(|e| e.to_string())
+}
+
+async fn check_internal(app: &AppHandle) -> Result<UpdateStatus, UpdateError> {
+    let state = app.state::<Arc<AppState>>();
+    state.set_update_status(UpdateStatus::Checking);
+
+    let channel = state.get_config().updates.channel;
+    let manifest = match fetch_manifest(&channel).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let status = UpdateStatus::Failed {
+                error: e.to_string(),
+            };
+            state.set_update_status(status.clone());
+            return Err(e);
+        }
+    };
+
+    let current = app.package_info().version.to_string();
+    let status = if is_newer(&manifest.version, &current) {
+        info!(
+            "Update available: {} (current {})",
+            manifest.version, current
+        );
+        let status = UpdateStatus::Available {
+            version: manifest.version.clone(),
+            notes: manifest.notes.clone(),
+        };
+        let _ = app.emit_all("update-available", &manifest);
+        status
+    } else {
+        info!("Already up to date ({})", current);
+        UpdateStatus::UpToDate
+    };
+
+    state.set_update_status(status.clone());
+    Ok(status)
+}
+
+/// 下载最新更新并暂存到缓存目录，过程中发 `update-download-progress`，完成后发
+/// `update-ready`。不执行安装，也不重启应用。
+#[tauri::command]
+pub async fn download_and_install_update(app: AppHandle) -> Result<UpdateStatus, String> {
+    download_internal(&app).await.map_err(|e| e.to_string())
+}
+
+async fn download_internal(app: &AppHandle) -> Result<UpdateStatus, UpdateError> {
+    let state = app.state::<Arc<AppState>>();
+
+    // 只有在已知有可用更新时才下载。
+    let channel = state.get_config().updates.channel;
+    let manifest = fetch_manifest(&channel).await?;
+    let current = app.package_info().version.to_string();
+    if !is_newer(&manifest.version, &current) {
+        return Err(UpdateError::NothingToInstall);
+    }
+
+    state.set_update_status(UpdateStatus::Downloading { progress: 0 });
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(&manifest.url)
+        .send()
+        .await
+        .map_err(|e| UpdateError::Download(e.to_string()))?;
+
+    let total = response.content_length().unwrap_or(0);
+    let mut downloaded: u64 = 0;
+    let mut last_emitted: u8 = 0;
+    let mut buffer: Vec<u8> = Vec::with_capacity(total as usize);
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| UpdateError::Download(e.to_string()))?
+    {
+        downloaded += chunk.len() as u64;
+        buffer.extend_from_slice(&chunk);
+
+        if total > 0 {
+            let progress = ((downloaded * 100) / total).min(100) as u8;
+            // 每增加一个百分点才推一次事件，避免刷屏。
+            if progress != last_emitted {
+                last_emitted = progress;
+                state.set_update_status(UpdateStatus::Downloading { progress });
+                let _ = app.emit_all("update-download-progress", progress);
+            }
+        }
+    }
+
+    // 落盘到缓存目录；这里不启动安装器，也不重启。
+    if let Some(dir) = crate::utils::paths::get_app_cache_dir() {
+        let _ = crate::utils::paths::ensure_dir_exists(&dir);
+        let target = dir.join(format!("okcvm-update-{}.bin", manifest.version));
+        if let Err(e) = std::fs::write(&target, &buffer) {
+            warn!("Failed to stage update artifact: {}", e);
+        } else {
+            info!("Staged update artifact at {}", target.display());
+        }
+    }
+
+    let status = UpdateStatus::Ready {
+        version: manifest.version.clone(),
+    };
+    state.set_update_status(status.clone());
+    let _ = app.emit_all("update-ready", &manifest.version);
+    Ok(status)
+}
+
+/// 按 `UpdatesConfig.auto_check` 在启动时检查一次，随后周期性检查。
+pub fn start_auto_update(app: &AppHandle) {
+    let state = app.state::<Arc<AppState>>();
+    if !state.get_config().updates.auto_check {
+        info!("Automatic update checks are disabled");
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = check_internal(&app).await {
+                error!("Update check failed: {}", e);
+            }
+            tokio::time::sleep(AUTO_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("1.2.0", "1.1.9"));
+        assert!(is_newer("2.0.0", "1.9.9"));
+        assert!(is_newer("1.0.1", "1.0.0"));
+        assert!(!is_newer("1.0.0", "1.0.0"));
+        assert!(!is_newer("1.0.0", "1.0.1"));
+        // 带前缀 v 与预发布后缀也能解析
+        assert!(is_newer("v1.2.0", "1.1.0"));
+        assert!(is_newer("1.2.0-beta.1", "1.1.0"));
+    }
+
+    #[test]
+    fn test_manifest_url_channel() {
+        assert!(manifest_url("beta").ends_with("/beta/latest.json"));
+        assert!(manifest_url("stable").ends_with("/stable/latest.json"));
+        // 未知渠道回退到 stable
+        assert!(manifest_url("nightly").ends_with("/stable/latest.json"));
+    }
+}
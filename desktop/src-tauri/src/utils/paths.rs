@@ -12,6 +12,11 @@ pub fn get_app_config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("okcvm"))
 }
 
+/// 获取配置文件路径（config.json）
+pub fn get_app_config_file() -> Option<PathBuf> {
+    get_app_config_dir().map(|p| p.join("config.json"))
+}
+
 /// 获取应用日志目录
 pub fn get_app_log_dir() -> Option<PathBuf> {
     #[cfg(target_os = "macos")]